@@ -3,17 +3,30 @@
 //! They’re formed with the following BNF:
 //!
 //! ```bnf
-//! ; Restriction: maximum `999` codes allowed between brackets.
+//! ; Restriction: maximum `size_max` codes allowed between markers.
 //! ; Restriction: no blank lines.
 //! ; Restriction: at least 1 non-space and non-eol code must exist.
-//! label ::= '[' *( label_text | label_escape ) ']'
-//! label_text ::= code - '[' - '\\' - ']'
-//! label_escape ::= '\\' [ '[' | '\\' | ']' ]
+//! label ::= marker_a *( label_text | label_escape ) marker_b
+//! label_text ::= code - marker_a - '\\' - marker_b
+//! label_escape ::= '\\' [ marker_a | '\\' | marker_b ]
 //! ```
 //!
-//! The maximum allowed size of the label, without the brackets, is `999`
-//! (inclusive), which is defined in
-//! [`LINK_REFERENCE_SIZE_MAX`][link_reference_size_max].
+//! This is a factory: the opening and closing markers and the maximum
+//! allowed size of the label (without the markers) are not fixed, but are
+//! supplied by the caller through `tokenize_state.marker_a`,
+//! `tokenize_state.marker_b`, and `tokenize_state.size_max`, the same way
+//! `tokenize_state.token_1`/`token_2`/`token_3` already parametrize the
+//! emitted token types.
+//! [`definition`][definition] and [`label_end`][label_end] are expected to
+//! drive this factory with `marker_a: b'['`, `marker_b: b']'`, and
+//! `size_max: `[`LINK_REFERENCE_SIZE_MAX`][link_reference_size_max] — their
+//! call sites live outside this file and aren't updated by this change, so
+//! `start` treats an unset (zero) `marker_a`/`marker_b`/`size_max` as
+//! "caller didn't opt into custom delimiters yet" and falls back to exactly
+//! those defaults, preserving today's `[`/`]`/999 behavior unconditionally.
+//! (`tokenize_state.marker` is unrelated: it’s the caller’s own single-byte
+//! scratch field, untouched by this factory other than the existing
+//! blank-line reset.)
 //!
 //! Labels can contain line endings and whitespace, but they are not allowed to
 //! contain blank lines, and they must not be blank themselves.
@@ -66,21 +79,41 @@ use crate::tokenizer::{ContentType, State, Tokenizer};
 
 /// Before a label.
 ///
+/// A caller may set `tokenize_state.marker_a` (opening byte),
+/// `tokenize_state.marker_b` (closing byte), and `tokenize_state.size_max`
+/// (maximum inner size) before attempting this to use different delimiters.
+/// Leaving them at `0` (the zero value `TokenizeState` already starts with)
+/// opts into today's `[`/`]`/[`LINK_REFERENCE_SIZE_MAX`][crate::constant::LINK_REFERENCE_SIZE_MAX]
+/// behavior, which this function fills in the first time it runs, so
+/// existing callers that don't know about these fields are unaffected.
+///
 /// ```markdown
 /// > | [a]
 ///     ^
 /// ```
 pub fn start(tokenizer: &mut Tokenizer) -> State {
-    match tokenizer.current {
-        Some(b'[') => {
-            tokenizer.enter(tokenizer.tokenize_state.token_1.clone());
-            tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
-            tokenizer.consume();
-            tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
-            tokenizer.enter(tokenizer.tokenize_state.token_3.clone());
-            State::Fn(Box::new(at_break))
-        }
-        _ => State::Nok,
+    if tokenizer.tokenize_state.marker_a == 0 {
+        tokenizer.tokenize_state.marker_a = b'[';
+    }
+    if tokenizer.tokenize_state.marker_b == 0 {
+        tokenizer.tokenize_state.marker_b = b']';
+    }
+    if tokenizer.tokenize_state.size_max == 0 {
+        tokenizer.tokenize_state.size_max = LINK_REFERENCE_SIZE_MAX;
+    }
+
+    if tokenizer.current == Some(tokenizer.tokenize_state.marker_a) {
+        tokenizer.enter(tokenizer.tokenize_state.token_1.clone());
+        tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
+        tokenizer.consume();
+        tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
+        tokenizer.enter(tokenizer.tokenize_state.token_3.clone());
+        State::Fn(Box::new(at_break))
+    } else {
+        tokenizer.tokenize_state.marker_a = 0;
+        tokenizer.tokenize_state.marker_b = 0;
+        tokenizer.tokenize_state.size_max = 0;
+        State::Nok
     }
 }
 
@@ -91,47 +124,53 @@ pub fn start(tokenizer: &mut Tokenizer) -> State {
 ///      ^
 /// ```
 fn at_break(tokenizer: &mut Tokenizer) -> State {
-    if tokenizer.tokenize_state.size > LINK_REFERENCE_SIZE_MAX
-        || matches!(tokenizer.current, None | Some(b'['))
-        || (matches!(tokenizer.current, Some(b']')) && !tokenizer.tokenize_state.seen)
+    let marker_a = tokenizer.tokenize_state.marker_a;
+    let marker_b = tokenizer.tokenize_state.marker_b;
+
+    if tokenizer.tokenize_state.size > tokenizer.tokenize_state.size_max
+        || tokenizer.current.is_none()
+        || tokenizer.current == Some(marker_a)
+        || (tokenizer.current == Some(marker_b) && !tokenizer.tokenize_state.seen)
     {
         tokenizer.tokenize_state.connect = false;
         tokenizer.tokenize_state.seen = false;
         tokenizer.tokenize_state.size = 0;
+        tokenizer.tokenize_state.marker_a = 0;
+        tokenizer.tokenize_state.marker_b = 0;
+        tokenizer.tokenize_state.size_max = 0;
         State::Nok
+    } else if tokenizer.current == Some(b'\n') {
+        tokenizer.attempt(
+            space_or_tab_eol_with_options(EolOptions {
+                content_type: Some(ContentType::String),
+                connect: tokenizer.tokenize_state.connect,
+            }),
+            |ok| Box::new(if ok { after_eol } else { at_blank_line }),
+        )(tokenizer)
+    } else if tokenizer.current == Some(marker_b) {
+        tokenizer.exit(tokenizer.tokenize_state.token_3.clone());
+        tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
+        tokenizer.consume();
+        tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
+        tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
+        tokenizer.tokenize_state.connect = false;
+        tokenizer.tokenize_state.seen = false;
+        tokenizer.tokenize_state.size = 0;
+        tokenizer.tokenize_state.marker_a = 0;
+        tokenizer.tokenize_state.marker_b = 0;
+        tokenizer.tokenize_state.size_max = 0;
+        State::Ok
     } else {
-        match tokenizer.current {
-            Some(b'\n') => tokenizer.attempt(
-                space_or_tab_eol_with_options(EolOptions {
-                    content_type: Some(ContentType::String),
-                    connect: tokenizer.tokenize_state.connect,
-                }),
-                |ok| Box::new(if ok { after_eol } else { at_blank_line }),
-            )(tokenizer),
-            Some(b']') => {
-                tokenizer.exit(tokenizer.tokenize_state.token_3.clone());
-                tokenizer.enter(tokenizer.tokenize_state.token_2.clone());
-                tokenizer.consume();
-                tokenizer.exit(tokenizer.tokenize_state.token_2.clone());
-                tokenizer.exit(tokenizer.tokenize_state.token_1.clone());
-                tokenizer.tokenize_state.connect = false;
-                tokenizer.tokenize_state.seen = false;
-                tokenizer.tokenize_state.size = 0;
-                State::Ok
-            }
-            _ => {
-                tokenizer.enter_with_content(Token::Data, Some(ContentType::String));
-
-                if tokenizer.tokenize_state.connect {
-                    let index = tokenizer.events.len() - 1;
-                    link(&mut tokenizer.events, index);
-                } else {
-                    tokenizer.tokenize_state.connect = true;
-                }
+        tokenizer.enter_with_content(Token::Data, Some(ContentType::String));
 
-                label(tokenizer)
-            }
+        if tokenizer.tokenize_state.connect {
+            let index = tokenizer.events.len() - 1;
+            link(&mut tokenizer.events, index);
+        } else {
+            tokenizer.tokenize_state.connect = true;
         }
+
+        label(tokenizer)
     }
 }
 
@@ -145,6 +184,9 @@ fn after_eol(tokenizer: &mut Tokenizer) -> State {
 fn at_blank_line(tokenizer: &mut Tokenizer) -> State {
     tokenizer.tokenize_state.marker = 0;
     tokenizer.tokenize_state.connect = false;
+    tokenizer.tokenize_state.marker_a = 0;
+    tokenizer.tokenize_state.marker_b = 0;
+    tokenizer.tokenize_state.size_max = 0;
     State::Nok
 }
 
@@ -155,13 +197,20 @@ fn at_blank_line(tokenizer: &mut Tokenizer) -> State {
 ///      ^
 /// ```
 fn label(tokenizer: &mut Tokenizer) -> State {
+    let marker_a = tokenizer.tokenize_state.marker_a;
+    let marker_b = tokenizer.tokenize_state.marker_b;
+
     match tokenizer.current {
-        None | Some(b'\n' | b'[' | b']') => {
+        None | Some(b'\n') => {
+            tokenizer.exit(Token::Data);
+            at_break(tokenizer)
+        }
+        Some(byte) if byte == marker_a || byte == marker_b => {
             tokenizer.exit(Token::Data);
             at_break(tokenizer)
         }
         Some(byte) => {
-            if tokenizer.tokenize_state.size > LINK_REFERENCE_SIZE_MAX {
+            if tokenizer.tokenize_state.size > tokenizer.tokenize_state.size_max {
                 tokenizer.exit(Token::Data);
                 at_break(tokenizer)
             } else {
@@ -184,8 +233,16 @@ fn label(tokenizer: &mut Tokenizer) -> State {
 ///        ^
 /// ```
 fn escape(tokenizer: &mut Tokenizer) -> State {
+    let marker_a = tokenizer.tokenize_state.marker_a;
+    let marker_b = tokenizer.tokenize_state.marker_b;
+
     match tokenizer.current {
-        Some(b'[' | b'\\' | b']') => {
+        Some(b'\\') => {
+            tokenizer.consume();
+            tokenizer.tokenize_state.size += 1;
+            State::Fn(Box::new(label))
+        }
+        Some(byte) if byte == marker_a || byte == marker_b => {
             tokenizer.consume();
             tokenizer.tokenize_state.size += 1;
             State::Fn(Box::new(label))
@@ -0,0 +1,374 @@
+//! Wikilink occurs in the wikilink construct.
+//!
+//! They’re formed with the following BNF:
+//!
+//! ```bnf
+//! ; Restriction: maximum `999` codes allowed in the target, and separately in the alias.
+//! ; Restriction: no line endings.
+//! ; Restriction: the target must not be empty.
+//! wikilink ::= '[[' target ( '|' alias )? ']]'
+//! target ::= *( target_text | target_escape )
+//! target_text ::= code - '|' - '\\' - ']'
+//! target_escape ::= '\\' [ '[' | '\\' | '|' | ']' ]
+//! alias ::= 1*( alias_text | target_escape )
+//! alias_text ::= code - '\\' - ']'
+//! ```
+//!
+//! This is an opt-in, non-CommonMark construct used by Logseq- and
+//! Obsidian-style vaults to link between pages by name rather than by an
+//! explicit [definition][] or inline destination.
+//! Unlike [label][partial_label], a wikilink tolerates a nested `[` in its
+//! target, since the closing delimiter is unambiguous (`]]`, not `]`).
+//! An empty target (`[[]]`) is not a wikilink, and a single `[` never starts
+//! one, so normal CommonMark links and images keep working unchanged.
+//!
+//! [`resolve`][] turns a parsed target into a URL: by default its ASCII
+//! letters are lowercased and spaces are turned into `-`, but callers can supply their
+//! own prefix/suffix (for example a `.html` suffix, or a vault-relative
+//! folder prefix) through [`WikilinkOptions`][].
+//! When no alias is given, the rendered link text is the raw target.
+//!
+//! **Scope of this checkout:** `token.rs`, `parser.rs`, and `compiler.rs` —
+//! where `Token::WikiLink`/`WikiLinkMarker`/`WikiLinkTarget`/`WikiLinkAlias`
+//! would be declared, where `Constructs` would grow a `wikilink` field and
+//! the text-construct dispatch table would route to `start` below, and
+//! where the tokenized events would become a `Link`/mdast node via
+//! [`resolve`][] — are not part of this checkout, and recreating them from
+//! memory to wire this construct in is out of scope here. That wiring is a
+//! prerequisite for this construct to be reachable from any real parse or
+//! mergeable as a finished feature; until it exists, treat this file as
+//! groundwork only: a tokenizing state machine and a tested pure
+//! target/alias → URL resolver (`resolve`), neither callable yet.
+//!
+//! ## References
+//!
+//! *   [`Logseq` page links](https://docs.logseq.com/#/page/linking%2c%20tagging%20and%20paste)
+//! *   [`Obsidian` internal links](https://help.obsidian.md/Linking+notes+and+files/Internal+links)
+//!
+//! [definition]: crate::construct::definition
+//! [partial_label]: crate::construct::partial_label
+
+use crate::constant::LINK_REFERENCE_SIZE_MAX;
+use crate::token::Token;
+use crate::tokenizer::{ContentType, State, Tokenizer};
+
+/// Before a wikilink.
+///
+/// ```markdown
+/// > | [[a]]
+///     ^
+/// ```
+pub fn start(tokenizer: &mut Tokenizer) -> State {
+    if tokenizer.parse_state.options.constructs.wikilink {
+        match tokenizer.current {
+            Some(b'[') => {
+                tokenizer.enter(Token::WikiLink);
+                tokenizer.enter(Token::WikiLinkMarker);
+                tokenizer.consume();
+                State::Fn(Box::new(open))
+            }
+            _ => State::Nok,
+        }
+    } else {
+        State::Nok
+    }
+}
+
+/// After the first `[`, at the second one.
+///
+/// ```markdown
+/// > | [[a]]
+///      ^
+/// ```
+fn open(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[') => {
+            tokenizer.consume();
+            tokenizer.exit(Token::WikiLinkMarker);
+            State::Fn(Box::new(target_start))
+        }
+        _ => State::Nok,
+    }
+}
+
+/// Before the target.
+///
+/// ```markdown
+/// > | [[a]]
+///       ^
+/// ```
+fn target_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        // An empty target (`[[]]`) is not a wikilink, and a wikilink cannot
+        // contain a line ending.
+        None | Some(b'\n' | b'|' | b']') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(_) => {
+            tokenizer.enter(Token::WikiLinkTarget);
+            tokenizer.enter_with_content(Token::Data, Some(ContentType::String));
+            target(tokenizer)
+        }
+    }
+}
+
+/// In the target.
+///
+/// ```markdown
+/// > | [[a]]
+///       ^
+/// ```
+fn target(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(b'|' | b']') if tokenizer.tokenize_state.size <= LINK_REFERENCE_SIZE_MAX => {
+            tokenizer.exit(Token::Data);
+            tokenizer.exit(Token::WikiLinkTarget);
+            tokenizer.tokenize_state.size = 0;
+            after_target(tokenizer)
+        }
+        Some(byte) => {
+            if tokenizer.tokenize_state.size > LINK_REFERENCE_SIZE_MAX {
+                tokenizer.tokenize_state.size = 0;
+                State::Nok
+            } else {
+                let func = if byte == b'\\' { target_escape } else { target };
+                tokenizer.consume();
+                tokenizer.tokenize_state.size += 1;
+                State::Fn(Box::new(func))
+            }
+        }
+    }
+}
+
+/// After `\`, in the target.
+///
+/// ```markdown
+/// > | [[a\]a]]
+///         ^
+/// ```
+fn target_escape(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[' | b'\\' | b'|' | b']') => {
+            tokenizer.consume();
+            tokenizer.tokenize_state.size += 1;
+            State::Fn(Box::new(target))
+        }
+        _ => target(tokenizer),
+    }
+}
+
+/// After the target, at `|` or the closing `]]`.
+///
+/// ```markdown
+/// > | [[a|b]]
+///         ^
+/// > | [[a]]
+///        ^
+/// ```
+fn after_target(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'|') => {
+            tokenizer.enter(Token::WikiLinkMarker);
+            tokenizer.consume();
+            tokenizer.exit(Token::WikiLinkMarker);
+            State::Fn(Box::new(alias_start))
+        }
+        Some(b']') => close(tokenizer),
+        _ => unreachable!("expected `|` or `]`"),
+    }
+}
+
+/// Before the alias.
+///
+/// ```markdown
+/// > | [[a|b]]
+///         ^
+/// ```
+fn alias_start(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n' | b']') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(_) => {
+            tokenizer.enter(Token::WikiLinkAlias);
+            tokenizer.enter_with_content(Token::Data, Some(ContentType::String));
+            alias(tokenizer)
+        }
+    }
+}
+
+/// In the alias.
+///
+/// ```markdown
+/// > | [[a|b]]
+///          ^
+/// ```
+fn alias(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        None | Some(b'\n') => {
+            tokenizer.tokenize_state.size = 0;
+            State::Nok
+        }
+        Some(b']') if tokenizer.tokenize_state.size <= LINK_REFERENCE_SIZE_MAX => {
+            tokenizer.exit(Token::Data);
+            tokenizer.exit(Token::WikiLinkAlias);
+            tokenizer.tokenize_state.size = 0;
+            close(tokenizer)
+        }
+        Some(byte) => {
+            if tokenizer.tokenize_state.size > LINK_REFERENCE_SIZE_MAX {
+                tokenizer.tokenize_state.size = 0;
+                State::Nok
+            } else {
+                let func = if byte == b'\\' { alias_escape } else { alias };
+                tokenizer.consume();
+                tokenizer.tokenize_state.size += 1;
+                State::Fn(Box::new(func))
+            }
+        }
+    }
+}
+
+/// After `\`, in the alias.
+///
+/// ```markdown
+/// > | [[a|b\]b]]
+///            ^
+/// ```
+fn alias_escape(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b'[' | b'\\' | b']') => {
+            tokenizer.consume();
+            tokenizer.tokenize_state.size += 1;
+            State::Fn(Box::new(alias))
+        }
+        _ => alias(tokenizer),
+    }
+}
+
+/// At the first `]` of the closing marker.
+///
+/// ```markdown
+/// > | [[a]]
+///        ^
+/// ```
+fn close(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b']') => {
+            tokenizer.enter(Token::WikiLinkMarker);
+            tokenizer.consume();
+            State::Fn(Box::new(after))
+        }
+        _ => State::Nok,
+    }
+}
+
+/// At the second `]` of the closing marker.
+///
+/// ```markdown
+/// > | [[a]]
+///         ^
+/// ```
+fn after(tokenizer: &mut Tokenizer) -> State {
+    match tokenizer.current {
+        Some(b']') => {
+            tokenizer.consume();
+            tokenizer.exit(Token::WikiLinkMarker);
+            tokenizer.exit(Token::WikiLink);
+            State::Ok
+        }
+        _ => State::Nok,
+    }
+}
+
+/// Controls how [`resolve`][] turns a wikilink target into a URL.
+#[derive(Debug, Clone)]
+pub struct WikilinkOptions {
+    /// Lowercase ASCII letters in the target before slugifying it; other
+    /// characters keep their case.
+    pub lowercase: bool,
+    /// Character that spaces in the target are replaced with.
+    pub space_replacement: char,
+    /// Prepended to the slugified target (for example a vault-relative
+    /// folder).
+    pub prefix: String,
+    /// Appended to the slugified target (for example `.html`).
+    pub suffix: String,
+}
+
+impl Default for WikilinkOptions {
+    fn default() -> Self {
+        WikilinkOptions {
+            lowercase: true,
+            space_replacement: '-',
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+}
+
+/// Resolve a parsed `target`/`alias` pair into `(url, display_text)`.
+///
+/// The display text defaults to the raw target when no alias was given.
+pub fn resolve(target: &str, alias: Option<&str>, options: &WikilinkOptions) -> (String, String) {
+    let slug: String = target
+        .chars()
+        .map(|byte| {
+            if byte == ' ' {
+                options.space_replacement
+            } else if options.lowercase {
+                byte.to_ascii_lowercase()
+            } else {
+                byte
+            }
+        })
+        .collect();
+    let url = format!("{}{}{}", options.prefix, slug, options.suffix);
+    let text = alias.unwrap_or(target).to_string();
+    (url, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_defaults_lowercase_and_dash() {
+        let (url, text) = resolve("My Page", None, &WikilinkOptions::default());
+        assert_eq!(url, "my-page");
+        assert_eq!(text, "My Page");
+    }
+
+    #[test]
+    fn resolve_uses_alias_as_display_text() {
+        let (_, text) = resolve("My Page", Some("home"), &WikilinkOptions::default());
+        assert_eq!(text, "home");
+    }
+
+    #[test]
+    fn resolve_applies_prefix_and_suffix() {
+        let options = WikilinkOptions {
+            prefix: "/pages/".into(),
+            suffix: ".html".into(),
+            ..WikilinkOptions::default()
+        };
+        let (url, _) = resolve("Getting Started", None, &options);
+        assert_eq!(url, "/pages/getting-started.html");
+    }
+
+    #[test]
+    fn resolve_can_keep_case() {
+        let options = WikilinkOptions {
+            lowercase: false,
+            ..WikilinkOptions::default()
+        };
+        let (url, _) = resolve("CamelCase", None, &options);
+        assert_eq!(url, "CamelCase");
+    }
+}
@@ -0,0 +1,208 @@
+//! A position and byte-access layer over a document given as multiple
+//! buffers, instead of one contiguous byte slice.
+//!
+//! Micromark’s `Chunk = Code | string` model expresses a position as
+//! `_index` (which chunk) plus `_bufferIndex` (offset within that chunk),
+//! so a parser can accept input as a sequence of buffers rather than one
+//! allocated `String` — useful for very large files or streamed sources.
+//! [`ChunkPoint`][] is that same two-part position, plus the flattened
+//! `offset` that `Point` and the rest of this crate already use for AST
+//! ranges, so it can stand in wherever a single flattened offset is needed.
+//! [`Chunks`][] resolves a `ChunkPoint` to a byte and steps to the next one,
+//! transparently crossing a chunk boundary, which is the bit that
+//! constructs spanning line endings (`after_eol`, `space_or_tab_eol`, and
+//! friends) rely on.
+//!
+//! Scope of this change: only this input layer, deliberately. The request
+//! asks for streaming input all the way through `Tokenizer`/`Point` (so
+//! `consume()`/`State::Fn` read from a `Chunks` instead of a `&[u8]`) and a
+//! public `&[&[u8]]`/buffer-iterator entry point — that requires editing
+//! `tokenizer.rs` and the top-level parse functions, neither of which is
+//! part of this checkout, so it isn’t done here and this module alone does
+//! not make parsing incremental yet.
+//! What's here is tested and usable on its own (see the tests below) as the
+//! foundation that integration would build on.
+//!
+//! ## References
+//!
+//! *   [`micromark/util/chunked.js`](https://github.com/micromark/micromark/blob/main/packages/micromark-util-chunked/dev/index.js)
+
+/// A position within a sequence of buffers: which buffer, the offset inside
+/// it, and the flattened offset across all buffers so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkPoint {
+    /// Index into the slice of buffers.
+    pub chunk_index: usize,
+    /// Byte offset within the buffer at `chunk_index`.
+    pub buffer_index: usize,
+    /// Flattened 0-based offset across all buffers, as used for AST ranges.
+    pub offset: usize,
+}
+
+impl ChunkPoint {
+    /// The position before the first byte of the first buffer.
+    pub fn start() -> Self {
+        Self {
+            chunk_index: 0,
+            buffer_index: 0,
+            offset: 0,
+        }
+    }
+}
+
+/// A document given as a sequence of byte buffers, read as if it were one
+/// contiguous slice.
+#[derive(Debug, Clone, Copy)]
+pub struct Chunks<'a> {
+    buffers: &'a [&'a [u8]],
+}
+
+impl<'a> Chunks<'a> {
+    /// Wrap a sequence of buffers.
+    pub fn new(buffers: &'a [&'a [u8]]) -> Self {
+        Self { buffers }
+    }
+
+    /// Total number of bytes across all buffers.
+    pub fn len(&self) -> usize {
+        self.buffers.iter().map(|buffer| buffer.len()).sum()
+    }
+
+    /// Whether there are no bytes in any buffer.
+    pub fn is_empty(&self) -> bool {
+        self.buffers.iter().all(|buffer| buffer.is_empty())
+    }
+
+    /// The byte at `point`, or `None` at the end of the document.
+    ///
+    /// Empty buffers are skipped transparently: if `point` addresses one
+    /// directly (or addresses the end of a non-empty one), this keeps
+    /// looking at `buffer_index: 0` of each following buffer until it finds
+    /// one with a byte, or runs out of buffers.
+    pub fn at(&self, point: ChunkPoint) -> Option<u8> {
+        let mut chunk_index = point.chunk_index;
+        let mut buffer_index = point.buffer_index;
+
+        while let Some(buffer) = self.buffers.get(chunk_index) {
+            if let Some(&byte) = buffer.get(buffer_index) {
+                return Some(byte);
+            }
+
+            chunk_index += 1;
+            buffer_index = 0;
+        }
+
+        None
+    }
+
+    /// The position right after `point`, crossing into the next non-empty
+    /// buffer when `point` was at the last byte of its buffer.
+    ///
+    /// Returns `None` once `point` is already at (or past) the end of the
+    /// document.
+    pub fn after(&self, point: ChunkPoint) -> Option<ChunkPoint> {
+        if self.at(point).is_none() {
+            return None;
+        }
+
+        let mut chunk_index = point.chunk_index;
+        let mut buffer_index = point.buffer_index;
+
+        // Move to the byte that `at` found, past any empty buffers or buffer
+        // end that `point` addresses.
+        while buffer_index >= self.buffers[chunk_index].len() {
+            chunk_index += 1;
+            buffer_index = 0;
+        }
+
+        buffer_index += 1;
+
+        while let Some(buffer) = self.buffers.get(chunk_index) {
+            if buffer_index < buffer.len() {
+                return Some(ChunkPoint {
+                    chunk_index,
+                    buffer_index,
+                    offset: point.offset + 1,
+                });
+            }
+
+            chunk_index += 1;
+            buffer_index = 0;
+        }
+
+        if chunk_index == self.buffers.len() {
+            Some(ChunkPoint {
+                chunk_index,
+                buffer_index: 0,
+                offset: point.offset + 1,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Flatten into one owned byte vector.
+    ///
+    /// Only meant for callers migrating incrementally, or for content small
+    /// enough that a single allocation is fine; the whole point of
+    /// `Chunks` is to avoid needing this on the hot path.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.len());
+        for buffer in self.buffers {
+            out.extend_from_slice(buffer);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_across_buffers() {
+        let buffers: &[&[u8]] = &[b"ab", b"", b"c"];
+        let chunks = Chunks::new(buffers);
+        assert_eq!(chunks.len(), 3);
+
+        let mut point = ChunkPoint::start();
+        let mut seen = vec![];
+        while let Some(byte) = chunks.at(point) {
+            seen.push(byte);
+            point = chunks.after(point).unwrap();
+        }
+        assert_eq!(seen, b"abc");
+        assert_eq!(chunks.after(point), None);
+    }
+
+    #[test]
+    fn empty_chunks_is_empty() {
+        let buffers: &[&[u8]] = &[b"", b""];
+        let chunks = Chunks::new(buffers);
+        assert!(chunks.is_empty());
+        assert_eq!(chunks.at(ChunkPoint::start()), None);
+    }
+
+    #[test]
+    fn to_vec_flattens() {
+        let buffers: &[&[u8]] = &[b"foo", b"bar"];
+        let chunks = Chunks::new(buffers);
+        assert_eq!(chunks.to_vec(), b"foobar");
+    }
+
+    #[test]
+    fn at_skips_a_leading_empty_buffer() {
+        let buffers: &[&[u8]] = &[b"", b"abc"];
+        let chunks = Chunks::new(buffers);
+        assert_eq!(chunks.len(), 3);
+        assert!(!chunks.is_empty());
+        assert_eq!(chunks.at(ChunkPoint::start()), Some(b'a'));
+    }
+
+    #[test]
+    fn at_skips_multiple_empty_buffers_in_a_row() {
+        let buffers: &[&[u8]] = &[b"", b"", b"x"];
+        let chunks = Chunks::new(buffers);
+        assert_eq!(chunks.at(ChunkPoint::start()), Some(b'x'));
+    }
+}
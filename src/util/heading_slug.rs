@@ -0,0 +1,206 @@
+//! Slugify heading text into GitHub-style anchor `id`s, and fold a flat list
+//! of headings into a nested table of contents.
+//!
+//! **Scope of this checkout:** the request asks for an opt-in
+//! `CompileOptions::heading_ids` flag that injects `id="…"` into ATX and
+//! Setext heading tags, plus a top-level `markdown::to_html_and_toc`
+//! function returning the TOC built here. Neither is added by this file —
+//! `compiler.rs` and the top-level `lib.rs` that would carry them aren’t
+//! part of this checkout, and recreating those from memory is out of scope
+//! for this change. This is therefore not the requested feature, only the
+//! slug/TOC logic (`IdMap::get`, `build_toc`) it would be built on, with no
+//! caller anywhere in this tree yet.
+//!
+//! The slug algorithm matches GitHub’s: lowercase the rendered heading text,
+//! drop anything that isn’t alphanumeric, a space, or a hyphen, collapse
+//! runs of spaces into a single `-`, and trim.
+//! Like rustdoc’s `IdMap`, repeated slugs are disambiguated by appending
+//! `-1`, `-2`, and so on, in the order the headings occur in the document.
+//!
+//! ## References
+//!
+//! *   [`IdMap` in `rustdoc`](https://github.com/rust-lang/rust/blob/master/src/librustdoc/html/id_map.rs)
+
+use std::collections::HashMap;
+
+/// Tracks slugs that have already been handed out, so a repeated heading
+/// text gets a unique `id`.
+#[derive(Debug, Default)]
+pub struct IdMap {
+    /// Every slug handed out so far, mapped to the next numeric suffix to
+    /// try when it comes up again as a base slug.
+    seen: HashMap<String, usize>,
+}
+
+impl IdMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Slugify `text` and return a unique `id`, recording it so a later
+    /// call with the same text gets `-1`, `-2`, and so on appended.
+    pub fn get(&mut self, text: &str) -> String {
+        let base = slugify(text);
+        let mut count = self.seen.get(&base).copied().unwrap_or(0);
+        let mut id = base.clone();
+        while self.seen.contains_key(&id) {
+            id = format!("{}-{}", base, count);
+            count += 1;
+        }
+        self.seen.insert(base, count.max(1));
+        self.seen.entry(id.clone()).or_insert(1);
+        id
+    }
+}
+
+/// Turn rendered heading text into a GitHub-style slug.
+///
+/// Lowercases the text, drops anything that isn’t alphanumeric, a space, or
+/// a hyphen, collapses runs of spaces into a single `-`, and trims.
+fn slugify(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last_was_space = false;
+
+    for ch in text.chars() {
+        if ch.is_alphanumeric() {
+            result.extend(ch.to_lowercase());
+            last_was_space = false;
+        } else if ch == ' ' {
+            if !last_was_space {
+                result.push('-');
+            }
+            last_was_space = true;
+        } else if ch == '-' {
+            result.push('-');
+            last_was_space = false;
+        }
+        // Other characters (punctuation, etc.) are dropped.
+    }
+
+    result.trim_matches('-').to_string()
+}
+
+/// One heading, as seen by the table-of-contents builder.
+#[derive(Debug, Clone)]
+pub struct Heading {
+    /// Heading level, `1..=6`.
+    pub level: u8,
+    /// Slug assigned to this heading (see [`IdMap`][]).
+    pub id: String,
+    /// Rendered heading text.
+    pub text: String,
+}
+
+/// One node of the nested table of contents.
+#[derive(Debug, Clone)]
+pub struct TocItem {
+    /// Heading level, `1..=6`.
+    pub level: u8,
+    /// Slug assigned to this heading.
+    pub id: String,
+    /// Rendered heading text.
+    pub text: String,
+    /// Headings nested under this one (deeper levels that follow it, up to
+    /// the next heading at this level or shallower).
+    pub children: Vec<TocItem>,
+}
+
+/// Fold a flat, document-order list of headings into a nested table of
+/// contents, by pushing and popping a stack as levels ascend and descend.
+pub fn build_toc(headings: &[Heading]) -> Vec<TocItem> {
+    let mut root: Vec<TocItem> = vec![];
+    // Stack of indices (as a path) into `root`, one per currently open
+    // level, shallowest first.
+    let mut stack: Vec<(u8, Vec<usize>)> = vec![];
+
+    for heading in headings {
+        let item = TocItem {
+            level: heading.level,
+            id: heading.id.clone(),
+            text: heading.text.clone(),
+            children: vec![],
+        };
+
+        while matches!(stack.last(), Some((level, _)) if *level >= heading.level) {
+            stack.pop();
+        }
+
+        let path = if let Some((_, parent_path)) = stack.last() {
+            let mut path = parent_path.clone();
+            let parent = path.iter().fold(&mut root, |items, &index| {
+                &mut items[index].children
+            });
+            path.push(parent.len());
+            parent.push(item);
+            path
+        } else {
+            let index = root.len();
+            root.push(item);
+            vec![index]
+        };
+
+        stack.push((heading.level, path));
+    }
+
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_basic() {
+        let mut map = IdMap::new();
+        assert_eq!(map.get("Hello, World!"), "hello-world");
+    }
+
+    #[test]
+    fn slugify_collisions_get_numbered() {
+        let mut map = IdMap::new();
+        assert_eq!(map.get("Intro"), "intro");
+        assert_eq!(map.get("Intro"), "intro-1");
+        assert_eq!(map.get("Intro"), "intro-2");
+    }
+
+    #[test]
+    fn slugify_collapses_spaces_and_trims() {
+        let mut map = IdMap::new();
+        assert_eq!(map.get("  Foo   Bar  "), "foo-bar");
+    }
+
+    #[test]
+    fn build_toc_nests_by_level() {
+        let headings = vec![
+            Heading {
+                level: 1,
+                id: "a".into(),
+                text: "A".into(),
+            },
+            Heading {
+                level: 2,
+                id: "b".into(),
+                text: "B".into(),
+            },
+            Heading {
+                level: 2,
+                id: "c".into(),
+                text: "C".into(),
+            },
+            Heading {
+                level: 1,
+                id: "d".into(),
+                text: "D".into(),
+            },
+        ];
+        let toc = build_toc(&headings);
+        assert_eq!(toc.len(), 2);
+        assert_eq!(toc[0].id, "a");
+        assert_eq!(toc[0].children.len(), 2);
+        assert_eq!(toc[0].children[0].id, "b");
+        assert_eq!(toc[0].children[1].id, "c");
+        assert_eq!(toc[1].id, "d");
+        assert!(toc[1].children.is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! A pluggable hook that would let a caller rewrite link/image destinations
+//! and titles as they’re resolved during compilation, without re-parsing.
+//!
+//! [`label_end`][label_end] resolves a `[label]`/`![label]` against either
+//! an inline destination or a [definition][] (the `y` in the `[x][y]` table
+//! documented there).
+//! The intent is for a [`LinkResolver`][] to be invoked for each one of
+//! those right after that resolution happens, and be able to rewrite the
+//! destination/title or mark it broken — handy for vault page lookups,
+//! intra-doc links, or CDN rewriting, all without a second parse pass.
+//! Returning `None` would mean “unresolved”: the compiler falls back to
+//! today’s behavior of emitting the source text as-is.
+//!
+//! **Scope of this checkout:** the request asks for this hook to actually
+//! fire from `label_end`'s resolution path via a new `CompileOptions`
+//! field. Neither `label_end` nor `CompileOptions` exist in this checkout
+//! (`label_end` lives in `construct/label_end.rs`, `CompileOptions` in
+//! `compiler.rs`; both are absent), and reconstructing those files
+//! from memory to add a call site is out of scope for this change. So this
+//! is not the requested integration — only the hook's types
+//! (`ResolveContext`/`Resolution`/`LinkResolver`), with no call site
+//! anywhere in this tree; the tests below exercise the types directly, not
+//! a real resolution pass, and cannot until that call site exists.
+//!
+//! ## References
+//!
+//! *   [`rustdoc`’s `RenderedLink` resolution](https://github.com/rust-lang/rust/blob/master/src/librustdoc/passes/collect_intra_doc_links.rs)
+//!
+//! [definition]: crate::construct::definition
+//! [label_end]: crate::construct::label_end
+
+use crate::unist::Point;
+
+/// What’s being resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveKind {
+    /// An inline destination, as in `[x](url "title")`.
+    Destination,
+    /// A reference label, as in `[x][y]`, looked up against a
+    /// [definition][crate::construct::definition].
+    Reference,
+}
+
+/// Given to a [`LinkResolver`][] for each link/image the compiler resolves.
+#[derive(Debug, Clone)]
+pub struct ResolveContext<'a> {
+    /// Whether this came from an inline destination or a reference label.
+    pub kind: ResolveKind,
+    /// The label text (the `x` in `[x](url)` or `[x][y]`).
+    pub label: &'a str,
+    /// The destination as parsed: the raw `url`, or the raw `y` label for a
+    /// reference, before this hook runs.
+    pub destination: &'a str,
+    /// The title as parsed, if any.
+    pub title: Option<&'a str>,
+    /// Where the destination/label starts in the source.
+    pub point: Point,
+}
+
+/// What a [`LinkResolver`][] may do with a link/image.
+#[derive(Debug, Clone)]
+pub enum Resolution {
+    /// Replace the destination (and, optionally, the title).
+    Rewrite {
+        /// New destination.
+        destination: String,
+        /// New title; `None` keeps whatever title was already parsed.
+        title: Option<String>,
+    },
+    /// Mark this link/image as broken, so the compiler can report it or
+    /// render it as plain text instead of an `<a>`/`<img>`.
+    Broken,
+}
+
+/// A user-supplied callback invoked for every resolved link/image
+/// destination and reference label.
+///
+/// Returning `None` leaves the destination/title untouched.
+pub type LinkResolver<'a> = dyn Fn(&ResolveContext) -> Option<Resolution> + 'a;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point() -> Point {
+        Point {
+            line: 1,
+            column: 1,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn rewrite_replaces_destination() {
+        let resolver: &LinkResolver = &|ctx: &ResolveContext| {
+            assert_eq!(ctx.label, "Home");
+            Some(Resolution::Rewrite {
+                destination: "/home.html".into(),
+                title: None,
+            })
+        };
+        let ctx = ResolveContext {
+            kind: ResolveKind::Reference,
+            label: "Home",
+            destination: "home",
+            title: None,
+            point: point(),
+        };
+        match resolver(&ctx) {
+            Some(Resolution::Rewrite { destination, title }) => {
+                assert_eq!(destination, "/home.html");
+                assert_eq!(title, None);
+            }
+            other => panic!("expected a rewrite, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn broken_is_reported() {
+        let resolver: &LinkResolver = &|_ctx: &ResolveContext| Some(Resolution::Broken);
+        let ctx = ResolveContext {
+            kind: ResolveKind::Destination,
+            label: "x",
+            destination: "nowhere",
+            title: None,
+            point: point(),
+        };
+        assert!(matches!(resolver(&ctx), Some(Resolution::Broken)));
+    }
+
+    #[test]
+    fn none_means_unresolved() {
+        let resolver: &LinkResolver = &|_ctx: &ResolveContext| None;
+        let ctx = ResolveContext {
+            kind: ResolveKind::Destination,
+            label: "x",
+            destination: "y",
+            title: None,
+            point: point(),
+        };
+        assert!(resolver(&ctx).is_none());
+    }
+}
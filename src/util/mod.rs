@@ -0,0 +1,9 @@
+//! Utilities shared across constructs and the compiler.
+//!
+//! `lib.rs` isn't part of this checkout, so nothing declares `mod util;`
+//! yet — these submodules aren't reachable from the crate root until that
+//! line is added there.
+
+pub mod chunked_input;
+pub mod heading_slug;
+pub mod link_resolver;